@@ -15,10 +15,22 @@ use std::{
         Layout
     },
     mem::{
-        size_of, 
+        align_of,
+        size_of,
         transmute
     },
-    ptr::null_mut
+    ops::{
+        Deref,
+        DerefMut
+    },
+    ptr::{
+        drop_in_place,
+        null_mut
+    },
+    sync::{
+        Mutex,
+        MutexGuard
+    }
 };
 
 #[cfg(test)]
@@ -36,14 +48,14 @@ mod tests {
         const SIZE_MAX: usize = 256;
         const COUNT_MAX: usize = 256;
 
-        let ptrs = [null_mut::<u8>(); 256];
+        let mut ptrs = [null_mut::<u8>(); 256];
 
         // サイズが1~256までで作成可能かテストします。
         for size in 1..SIZE_MAX {
             // 要素数が1~256までで作成可能かテストします。
             for count in 1..COUNT_MAX {
                 // 作成に成功するかテストします。
-                let pool = if let Some(pool) = Pool::new(size, count) {
+                let mut pool = if let Some(pool) = Pool::new(size, count) {
                     pool
                 } else {
                     panic!("サイズ:{} 要素数:{} で作成に失敗しました。", size, count)
@@ -82,29 +94,259 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_pool_growable() {
+        const COUNT: usize = 8;
+
+        // 非成長プールは枯渇するとヌルポインタを返すかテストします。
+        let mut fixed = Pool::new(4, COUNT).expect("固定プールの作成に失敗しました。");
+        for _ in 0..COUNT {
+            assert_ne!(fixed.alloc(), null_mut(), "固定プールの確保に失敗しました。");
+        }
+        assert_eq!(fixed.alloc(), null_mut(), "固定プールが枯渇後もヌルを返しませんでした。");
+
+        // 成長プールは枯渇時に新しいブロックを連結し、確保を続けられるかテストします。
+        let mut growable = Pool::new_growable(4, COUNT).expect("成長プールの作成に失敗しました。");
+        let mut ptrs = Vec::new();
+        for _ in 0..COUNT * 3 {
+            let ptr = growable.alloc();
+            assert_ne!(ptr, null_mut(), "成長プールの確保に失敗しました。");
+            assert!(growable.is_manage(ptr), "成長したブロックが管理範囲と認識されませんでした。");
+            ptrs.push(ptr);
+        }
+
+        // 追加ブロックを含め正しく解放できるかテストします。
+        for ptr in ptrs {
+            assert!(growable.dealloc(ptr), "成長したブロックの要素を解放できませんでした。");
+        }
+    }
+
+    #[test]
+    fn test_pool_bitmap() {
+        let mut pool = Pool::new(4, 4).expect("プールの作成に失敗しました。");
+        let ptr = pool.alloc();
+        assert_ne!(ptr, null_mut(), "確保に失敗しました。");
+
+        // 1回目の解放は成功するかテストします。
+        assert!(pool.dealloc(ptr), "解放に失敗しました。");
+
+        // 同じポインタの2回目の解放は二重解放として拒否されるかテストします。
+        assert!(!pool.dealloc(ptr), "二重解放が拒否されませんでした。");
+
+        // 管理外のポインタの解放が拒否されるかテストします。
+        let mut foreign = [0u8; 4];
+        assert!(!pool.dealloc(foreign.as_mut_ptr()), "管理外のポインタの解放が拒否されませんでした。");
+    }
+
+    #[test]
+    fn test_pool_alloc_zeroed() {
+        let mut pool = Pool::new(8, 4).expect("プールの作成に失敗しました。");
+
+        // ゼロ初期化された領域が確保されるかテストします。
+        let ptr = pool.alloc_zeroed();
+        assert_ne!(ptr, null_mut(), "確保に失敗しました。");
+        for i in 0..8 {
+            assert_eq!(unsafe { *ptr.add(i) }, 0, "{}バイト目がゼロ初期化されていません。", i);
+        }
+    }
+
+    #[test]
+    fn test_pool_boxed() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        struct Droppable(u32);
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                DROPPED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut pool = Pool::new(size_of::<Droppable>(), 1).expect("プールの作成に失敗しました。");
+
+        // 値の書き込みと参照先からの読み取りができるかテストします。
+        {
+            let mut boxed = pool.boxed(Droppable(42)).expect("boxed化に失敗しました。");
+            assert_eq!(boxed.0, 42, "書き込んだ値が読み取れませんでした。");
+            boxed.0 = 7;
+            assert_eq!(boxed.0, 7, "書き換えた値が読み取れませんでした。");
+        }
+        // スコープを抜けるとデストラクタが実行されるかテストします。
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 1, "Dropでデストラクタが実行されませんでした。");
+
+        // スロットがプールへ返却され、再利用できるかテストします。
+        let reused = pool.boxed(Droppable(1)).expect("返却されたスロットの再利用に失敗しました。");
+        drop(reused);
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 2, "再利用したスロットのDropでデストラクタが実行されませんでした。");
+
+        // 要素サイズに収まらない型はNoneが返るかテストします。
+        assert!(pool.boxed([0u8; 4096]).is_none(), "収まらない型でboxed化に成功してしまいました。");
+    }
+
+    #[test]
+    fn test_sync_pool() {
+        use std::sync::Arc;
+        use std::thread;
+
+        const COUNT: usize = 256;
+        const THREADS: usize = 8;
+
+        let pool = Arc::new(SyncPool::new(8, COUNT).expect("プールの作成に失敗しました。"));
+
+        // 複数スレッドから同時に確保しても、重複や喪失なく要素数ちょうど確保できるかテストします。
+        // (生ポインタはSendではないため、スレッド境界をusizeとして跨がせます。)
+        let handles: Vec<_> = (0..THREADS).map(|_| {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                let mut addrs = Vec::with_capacity(COUNT / THREADS);
+                loop {
+                    let ptr = pool.alloc();
+                    if ptr == null_mut() {
+                        break;
+                    }
+                    addrs.push(ptr as usize);
+                }
+                addrs
+            })
+        }).collect();
+
+        let mut all_addrs: Vec<usize> = handles.into_iter()
+            .flat_map(|handle| handle.join().expect("スレッドの待機に失敗しました。"))
+            .collect();
+        all_addrs.sort();
+        all_addrs.dedup();
+        assert_eq!(all_addrs.len(), COUNT, "複数スレッドからの確保で重複、または、喪失が発生しました。");
+
+        // 確保したアドレスが管理範囲として認識されるかテストします。
+        for &addr in &all_addrs {
+            assert!(pool.is_manage(addr as *mut u8), "確保したアドレスが管理範囲からはじかれました。");
+        }
+
+        // 枯渇後はヌルポインタが返るかテストします。
+        assert_eq!(pool.alloc(), null_mut(), "枯渇後もヌルを返しませんでした。");
+
+        // 複数スレッドから同時に解放しても、全て成功するかテストします。
+        let chunk_size = COUNT / THREADS;
+        let handles: Vec<_> = all_addrs.chunks(chunk_size).map(|chunk| {
+            let pool = pool.clone();
+            let chunk = chunk.to_vec();
+            thread::spawn(move || {
+                chunk.into_iter().all(|addr| pool.dealloc(addr as *mut u8))
+            })
+        }).collect();
+        for handle in handles {
+            assert!(handle.join().expect("スレッドの待機に失敗しました。"), "複数スレッドからの解放に失敗しました。");
+        }
+    }
+
+    #[test]
+    fn test_static_memory_pool() {
+        // バケット構成が空でも作成できるかテストします。
+        assert!(StaticMemoryPool::new(&[]).is_some(), "空のバケット構成で作成に失敗しました。");
+
+        // サイズ、または、要素数0のバケットを含む場合に失敗するかテストします。
+        assert!(StaticMemoryPool::new(&[(1, 0)]).is_none(), "サイズ0のバケットで失敗しません。");
+
+        // 昇順でないバケット構成からでも正しくルーティングされるかテストします。
+        let mut pool = StaticMemoryPool::new(&[(4, 32), (4, 4), (4, 16), (4, 8)])
+            .expect("バケット構成からの作成に失敗しました。");
+
+        // 要求サイズ以上で最小のバケットへルーティングされるかテストします。
+        let addr_small = pool.alloc(3).expect("サイズ3の確保に失敗しました。");
+        let addr_mid = pool.alloc(9).expect("サイズ9の確保に失敗しました。");
+        let addr_large = pool.alloc(32).expect("サイズ32の確保に失敗しました。");
+        assert_eq!(addr_small.bucket, 0, "サイズ3が要素サイズ8のバケットへルーティングされませんでした。");
+        assert_eq!(addr_mid.bucket, 2, "サイズ9が要素サイズ16のバケットへルーティングされませんでした。");
+        assert_eq!(addr_large.bucket, 3, "サイズ32が要素サイズ32のバケットへルーティングされませんでした。");
+
+        // 要求を満たすバケットが無い場合Noneが返るかテストします。
+        assert!(pool.alloc(33).is_none(), "バケットを超えるサイズで確保できてしまいました。");
+
+        // アドレス経由で値の書き込みと読み取りができるかテストします。
+        unsafe {
+            assert!(pool.modify(addr_small, 1u8), "値の書き込みに失敗しました。");
+            assert!(pool.modify(addr_mid, 2u8), "値の書き込みに失敗しました。");
+            assert!(pool.modify(addr_large, 3u8), "値の書き込みに失敗しました。");
+            assert_eq!(pool.read::<u8>(addr_small), Some(1u8), "値の読み取りに失敗しました。");
+            assert_eq!(pool.read::<u8>(addr_mid), Some(2u8), "値の読み取りに失敗しました。");
+            assert_eq!(pool.read::<u8>(addr_large), Some(3u8), "値の読み取りに失敗しました。");
+        }
+
+        // 解放できるかテストします。
+        assert!(pool.dealloc(addr_small), "解放に失敗しました。");
+        assert!(pool.dealloc(addr_mid), "解放に失敗しました。");
+        assert!(pool.dealloc(addr_large), "解放に失敗しました。");
+
+        // 存在しないバケットへのアドレスで失敗するかテストします。
+        let addr_invalid = PoolAddr { bucket: 99, slot: 0 };
+        assert!(!pool.dealloc(addr_invalid), "存在しないバケットの解放に成功してしまいました。");
+    }
+
+    #[test]
+    fn test_static_memory_pool_duplicate_bucket_fallback() {
+        // 整列後の要素サイズが重複するバケット構成でも作成できるかテストします。
+        let mut pool = StaticMemoryPool::new(&[(1, 4), (1, 4)])
+            .expect("重複した要素サイズのバケット構成からの作成に失敗しました。");
+
+        // 1つ目のバケットが枯渇しても、同じ要素サイズの2つ目のバケットへ
+        // 確保が続けられるかテストします。
+        assert!(pool.alloc(4).is_some(), "1つ目のバケットからの確保に失敗しました。");
+        assert!(pool.alloc(4).is_some(), "2つ目のバケットへフォールバックできませんでした。");
+
+        // 全バケットが枯渇した後はNoneが返るかテストします。
+        assert!(pool.alloc(4).is_none(), "全バケット枯渇後もNoneが返りませんでした。");
+    }
 }
 
+/// ビットマップの1ワードが管理するビット数です。
+const CAPACITY: usize = 32;
+
 /// メモリ領域を複数の要素として管理します。
 #[derive(Debug)]
 struct Pool {
-    elements_count: usize, // 管理対象の要素数です。
-    current_count: usize,  // 現在確保している要素数です。
-    layout: Layout,        // メモリ領域のレイアウトです。
-    buffer: *mut u8,       // メモリ領域です。
-    top: *mut *mut u8,     // 要素の単方向連結リストの先頭です。
+    elements_count: usize,         // 1ブロックあたりの要素数です。
+    current_count: usize,          // 現在確保可能な要素数です。
+    element_size: usize,           // 1要素の整列後のサイズです。
+    layout: Layout,                // 1ブロックのレイアウトです。
+    buffer: *mut u8,               // 最初に確保したブロックです。
+    extra_blocks: Vec<*mut u8>,    // 枯渇時に追加で確保したブロックです。
+    growable: bool,                // 枯渇時に新しいブロックを確保するかどうかです。
+    occupancy: Vec<u32>,           // 全スロットの使用状況を1ビットずつ保持するビットマップです。
+    top: *mut *mut u8,             // 要素の単方向連結リストの先頭です。
 }
 impl Pool {
-    /// プールを作成します。
+    /// プールを作成します。枯渇した際は`alloc`がヌルポインタを返します。
     ///
     /// # 引数
-    /// 
+    ///
     /// * size - 要素のサイズです。(ポインタサイズ以上に矯正されます。)
     /// * count - 要素数です。
-    /// 
+    ///
     /// # 戻り値
-    /// 
+    ///
     /// 成功した際はインスタンス、失敗した際はNoneが返ります。
     fn new(size: usize, count: usize) -> Option<Pool> {
+        Self::build(size, count, false)
+    }
+
+    /// 枯渇時に新しいブロックを連結して自動的に成長するプールを作成します。
+    ///
+    /// # 引数
+    ///
+    /// * size - 要素のサイズです。(ポインタサイズ以上に矯正されます。)
+    /// * count - 1ブロックあたりの要素数です。
+    ///
+    /// # 戻り値
+    ///
+    /// 成功した際はインスタンス、失敗した際はNoneが返ります。
+    fn new_growable(size: usize, count: usize) -> Option<Pool> {
+        Self::build(size, count, true)
+    }
+
+    /// プールを作成します。
+    fn build(size: usize, count: usize, growable: bool) -> Option<Pool> {
         // サイズ、または、要素数0の場合作成されません。
         if size == 0 || count == 0 {
             return None;
@@ -114,60 +356,466 @@ impl Pool {
         const PTR_SIZE: usize = size_of::<*mut u8>();
         let size = if size < PTR_SIZE { PTR_SIZE } else { size };
         let align = size.next_power_of_two();
-        
+
         // 領域のサイズと整列長です。
         let buf_size = align * count;
         let buf_align = buf_size.next_power_of_two();
-        
+
         // 領域を確保します。
         let layout = unsafe { Layout::from_size_align_unchecked(buf_size, buf_align) };
         let buffer = unsafe { alloc(layout) };
         if buffer == null_mut() {
-            return None;        
+            return None;
         }
-        
+
+        let top = Self::link(buffer, align, count, null_mut());
+
+        Some(Pool{
+            elements_count: count,
+            current_count: count,
+            element_size: align,
+            layout,
+            buffer,
+            extra_blocks: Vec::new(),
+            growable,
+            occupancy: vec![0u32; Self::word_count(count)],
+            top
+        })
+    }
+
+    /// 指定したスロット数を表すのに必要なビットマップのワード数を求めます。
+    fn word_count(slots: usize) -> usize {
+        slots.div_ceil(CAPACITY)
+    }
+
+    /// ブロック内の要素を単方向連結リストとして繋ぎます。
+    ///
+    /// # 引数
+    ///
+    /// * block - 連結するブロックの先頭です。
+    /// * align - 1要素の整列後のサイズです。
+    /// * count - ブロック内の要素数です。
+    /// * tail - リストの末尾に繋ぐ既存の先頭です。
+    ///
+    /// # 戻り値
+    ///
+    /// 新しいリストの先頭です。
+    fn link(block: *mut u8, align: usize, count: usize, tail: *mut *mut u8) -> *mut *mut u8 {
         // 連結リストを作成します。
-        // 
-        //     buffer [ptr][ptr][ptr]...
+        //
+        //     block  [ptr][ptr][ptr]...
         //            | ^  | ^  | ^
-        // null_mut <-' '--' '--' '-- top
+        // tail <-----' '--' '--' '-- 戻り値
         //
-        let mut top = null_mut();
+        let mut top = tail;
         for i in 0..count {
-            let lp = unsafe { buffer.add(i * align) }; 
+            let lp = unsafe { block.add(i * align) };
             let lpp = unsafe { transmute::<*mut u8, *mut *mut u8>(lp) };
             let rp = unsafe { transmute::<*mut *mut u8, *mut u8>(top) };
             unsafe { *lpp = rp };
-            top = lpp; 
+            top = lpp;
         }
-
-        Some(Pool{ elements_count: count, current_count: count, layout, buffer, top })
+        top
     }
 
-    /// 
+    /// 枯渇時に新しいブロックを確保し、自由リストへ連結します。
+    ///
+    /// # 戻り値
+    ///
+    /// 成長できた際はtrue、確保に失敗した際はfalseが返ります。
+    fn grow(&mut self) -> bool {
+        let block = unsafe { alloc(self.layout) };
+        if block == null_mut() {
+            return false;
+        }
+
+        self.top = Self::link(block, self.element_size, self.elements_count, self.top);
+        self.extra_blocks.push(block);
+        self.current_count += self.elements_count;
+        self.occupancy.resize(Self::word_count(self.elements_count * (1 + self.extra_blocks.len())), 0);
+        true
+    }
 
     /// 要素を確保します。
-    /// 
+    ///
     /// # 戻り値
-    /// 
+    ///
     /// 確保したメモリへのポインタ、または、ヌルポインタです。
-    /// 
     fn alloc(&mut self) -> *mut u8 {
+        if self.top == null_mut() && self.growable {
+            self.grow();
+        }
+
         if self.top != null_mut() {
-            self.count -= 1;
+            self.current_count -= 1;
             let ptr = self.top;
             unsafe { self.top = transmute::<*mut u8, *mut *mut u8>(*ptr) };
-            unsafe { transmute::<*mut *mut u8, *mut u8>(ptr) }
+            let ptr = unsafe { transmute::<*mut *mut u8, *mut u8>(ptr) };
+
+            // 確保したスロットのビットを立てます。
+            if let Some(index) = self.slot_index(ptr) {
+                let (word, bit) = (index / CAPACITY, index % CAPACITY);
+                self.occupancy[word] |= 1 << bit;
+            }
+
+            ptr
         } else {
             null_mut()
         }
     }
 
-    
+    /// 要素を解放します。二重解放や管理外のポインタは拒否します。
+    ///
+    /// # 引数
+    ///
+    /// * ptr - 解放する要素へのポインタです。
+    ///
+    /// # 戻り値
+    ///
+    /// 解放に成功した際はtrue、管理外のポインタ、または、二重解放だった際はfalseが返ります。
+    fn dealloc(&mut self, ptr: *mut u8) -> bool {
+        let index = match self.slot_index(ptr) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        // ビットが既に立っていない場合、二重解放、または、不正なポインタです。
+        let (word, bit) = (index / CAPACITY, index % CAPACITY);
+        if self.occupancy[word] & (1 << bit) == 0 {
+            return false;
+        }
+        self.occupancy[word] &= !(1 << bit);
+
+        let lpp = unsafe { transmute::<*mut u8, *mut *mut u8>(ptr) };
+        let rp = unsafe { transmute::<*mut *mut u8, *mut u8>(self.top) };
+        unsafe { *lpp = rp };
+        self.top = lpp;
+        self.current_count += 1;
+        true
+    }
+
+    /// ポインタが自身の管理するメモリ領域を指しているか判定します。
+    /// 成長済みの場合、全てのブロックを対象に判定します。
+    ///
+    /// # 引数
+    ///
+    /// * ptr - 判定するポインタです。
+    ///
+    /// # 戻り値
+    ///
+    /// いずれかのブロック内で要素境界に整列している際はtrue、それ以外はfalseが返ります。
+    fn is_manage(&self, ptr: *mut u8) -> bool {
+        self.slot_index(ptr).is_some()
+    }
+
+    /// ポインタが指すスロットの通し番号を求めます。
+    /// 先頭ブロックを0番として、成長で追加したブロックの分だけ続きます。
+    ///
+    /// # 引数
+    ///
+    /// * ptr - 判定するポインタです。
+    ///
+    /// # 戻り値
+    ///
+    /// 管理範囲内で要素境界に整列している際はそのスロット番号、それ以外はNoneが返ります。
+    fn slot_index(&self, ptr: *mut u8) -> Option<usize> {
+        let p = ptr as usize;
+        let locate = |block: *mut u8| {
+            let start = block as usize;
+            let end = start + self.layout.size();
+            if p >= start && p < end && (p - start).is_multiple_of(self.element_size) {
+                Some((p - start) / self.element_size)
+            } else {
+                None
+            }
+        };
+
+        if let Some(offset) = locate(self.buffer) {
+            return Some(offset);
+        }
+        for (i, &block) in self.extra_blocks.iter().enumerate() {
+            if let Some(offset) = locate(block) {
+                return Some((i + 1) * self.elements_count + offset);
+            }
+        }
+        None
+    }
+
+    /// ゼロ初期化された要素を確保します。
+    ///
+    /// # 戻り値
+    ///
+    /// 確保したメモリへのポインタ、または、ヌルポインタです。
+    fn alloc_zeroed(&mut self) -> *mut u8 {
+        let ptr = self.alloc();
+        if ptr != null_mut() {
+            unsafe { ptr.write_bytes(0, self.element_size) };
+        }
+        ptr
+    }
+
+    /// 値を書き込んだ要素を確保し、Dropで自動的に解放されるハンドルとして返します。
+    /// 生のポインタを扱うことなく、安全に`Pool`を利用できます。
+    ///
+    /// # 引数
+    ///
+    /// * value - 確保した要素に書き込む値です。
+    ///
+    /// # 戻り値
+    ///
+    /// 成功した際は`PoolBox`、`T`がバケットに収まらない、または、確保に
+    /// 失敗した際はNoneが返ります。
+    fn boxed<T>(&mut self, value: T) -> Option<PoolBox<'_, T>> {
+        if size_of::<T>() > self.element_size || align_of::<T>() > self.element_size {
+            return None;
+        }
+
+        let ptr = self.alloc();
+        if ptr == null_mut() {
+            return None;
+        }
+
+        let ptr = ptr.cast::<T>();
+        unsafe { ptr.write(value) };
+        Some(PoolBox { pool: self, ptr })
+    }
 }
 impl Drop for Pool {
-    /// プールを解体します。
+    /// プールを解体します。成長で追加したブロックも全て解放します。
     fn drop(&mut self) {
         unsafe { dealloc(self.buffer, self.layout) };
+        for &block in &self.extra_blocks {
+            unsafe { dealloc(block, self.layout) };
+        }
     }
-}
\ No newline at end of file
+}
+
+/// `Pool::boxed`が返す、値への排他参照を保証するRAIIハンドルです。
+///
+/// `Box`のように`Deref`/`DerefMut`で中身の`T`を直接扱え、Dropの際に
+/// `T`のデストラクタを実行したうえでスロットを自動的に`Pool`へ返却します。
+/// これにより、呼び出し側が生ポインタの確保・解放を管理する必要がなくなります。
+struct PoolBox<'p, T> {
+    pool: &'p mut Pool, // 要素を貸し出したプールです。
+    ptr: *mut T,         // 貸し出された要素へのポインタです。
+}
+impl<T> Deref for PoolBox<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.ptr }
+    }
+}
+impl<T> DerefMut for PoolBox<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.ptr }
+    }
+}
+impl<T> Drop for PoolBox<'_, T> {
+    /// `T`のデストラクタを実行し、スロットをプールへ返却します。
+    fn drop(&mut self) {
+        unsafe { drop_in_place(self.ptr) };
+        self.pool.dealloc(self.ptr.cast::<u8>());
+    }
+}
+
+/// メモリ領域を複数の要素として管理する、スレッドセーフな`Pool`です。
+///
+/// 以前はTreiberスタックによりミューテックス無しで`alloc`/`dealloc`して
+/// いましたが、自由リストの`next`がスロットに埋め込まれ生ポインタとして
+/// 使い回される構造では、タグ付きポインタやハザードポインタ等の世代管理
+/// なしにABAやデータ競合を避けられないことが判明しました。確保した
+/// スロットへ呼び出し側が値を書き込むという通常の利用そのものが、その
+/// 書き込みと他スレッドの`next`読み取りを競合させてしまうためです。
+/// そのため内部の`Pool`を`Mutex`で保護する方式に置き換えています。
+/// `alloc`/`dealloc`ともに複数スレッドから安全に呼び出せます。
+#[derive(Debug)]
+struct SyncPool {
+    pool: Mutex<Pool>, // ミューテックスで保護された`Pool`本体です。
+}
+unsafe impl Send for SyncPool {}
+unsafe impl Sync for SyncPool {}
+impl SyncPool {
+    /// プールを作成します。
+    ///
+    /// # 引数
+    ///
+    /// * size - 要素のサイズです。(ポインタサイズ以上に矯正されます。)
+    /// * count - 要素数です。
+    ///
+    /// # 戻り値
+    ///
+    /// 成功した際はインスタンス、失敗した際はNoneが返ります。
+    fn new(size: usize, count: usize) -> Option<SyncPool> {
+        Some(SyncPool { pool: Mutex::new(Pool::new(size, count)?) })
+    }
+
+    /// 要素を確保します。内部でロックを取得するため、複数スレッドから
+    /// 同時に呼び出せます。
+    ///
+    /// # 戻り値
+    ///
+    /// 確保したメモリへのポインタ、または、ヌルポインタです。
+    fn alloc(&self) -> *mut u8 {
+        self.lock().alloc()
+    }
+
+    /// 要素を解放します。内部でロックを取得するため、複数スレッドから
+    /// 同時に呼び出せます。
+    ///
+    /// # 引数
+    ///
+    /// * ptr - 解放する要素へのポインタです。
+    ///
+    /// # 戻り値
+    ///
+    /// 解放に成功した際はtrue、管理外のポインタ、または、二重解放だった際はfalseが返ります。
+    fn dealloc(&self, ptr: *mut u8) -> bool {
+        self.lock().dealloc(ptr)
+    }
+
+    /// ポインタが自身の管理するメモリ領域を指しているか判定します。
+    ///
+    /// # 引数
+    ///
+    /// * ptr - 判定するポインタです。
+    ///
+    /// # 戻り値
+    ///
+    /// 管理領域内で要素境界に整列している際はtrue、それ以外はfalseが返ります。
+    fn is_manage(&self, ptr: *mut u8) -> bool {
+        self.lock().is_manage(ptr)
+    }
+
+    /// 内部の`Pool`のロックを取得します。他スレッドがロックを保持した
+    /// ままパニックした場合でも、中身を取り出して利用を継続します。
+    fn lock(&self) -> MutexGuard<'_, Pool> {
+        self.pool.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// `StaticMemoryPool`内の要素を指す不透明なアドレスです。
+///
+/// 生ポインタの代わりにバケット番号とスロット番号を保持するため、
+/// `dealloc`や`read`/`modify`は所有するバケットを走査せずO(1)で特定できます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PoolAddr {
+    bucket: u16, // 所属するバケットの番号です。
+    slot: u32,   // バケット内のスロット番号です。
+}
+
+/// 要素サイズの異なる複数の`Pool`をバケットとして束ね、サイズ別に振り分けて管理します。
+///
+/// バケットは要素サイズの昇順に並び替えられ、`alloc`は要求サイズ以上で
+/// 最小のバケットへルーティングします。ECSコンポーネントのようにサイズの
+/// 異なるオブジェクトを、サイズごとに`Pool`を用意せずまとめて管理できます。
+#[derive(Debug)]
+struct StaticMemoryPool {
+    pools: Vec<Pool>, // 要素サイズ昇順に並んだバケットです。
+}
+impl StaticMemoryPool {
+    /// バケット構成からプールを作成します。
+    ///
+    /// # 引数
+    ///
+    /// * buckets - (要素数, 要素サイズ)の組のリストです。
+    ///
+    /// # 戻り値
+    ///
+    /// 全バケットの作成に成功した際はインスタンス、失敗した際はNoneが返ります。
+    fn new(buckets: &[(usize, usize)]) -> Option<StaticMemoryPool> {
+        let mut pools = Vec::with_capacity(buckets.len());
+        for &(count, size) in buckets {
+            pools.push(Pool::new(size, count)?);
+        }
+        pools.sort_by_key(|pool| pool.element_size);
+        Some(StaticMemoryPool { pools })
+    }
+
+    /// 要求サイズ以上で最小のバケットから要素を確保します。
+    /// 選ばれたバケットが枯渇している場合、要素サイズが同じ、または、
+    /// より大きい次のバケットへ順に確保を試みます。
+    ///
+    /// # 引数
+    ///
+    /// * size - 要求する要素のサイズです。
+    ///
+    /// # 戻り値
+    ///
+    /// 確保した要素のアドレス、または、確保できなかった際はNoneが返ります。
+    fn alloc(&mut self, size: usize) -> Option<PoolAddr> {
+        let start = self.pools.iter().position(|pool| pool.element_size >= size)?;
+        for bucket in start..self.pools.len() {
+            let pool = &mut self.pools[bucket];
+            let ptr = pool.alloc();
+            if ptr != null_mut() {
+                let slot = (ptr as usize - pool.buffer as usize) / pool.element_size;
+                return Some(PoolAddr { bucket: bucket as u16, slot: slot as u32 });
+            }
+        }
+        None
+    }
+
+    /// 要素を解放します。
+    ///
+    /// # 引数
+    ///
+    /// * addr - 解放する要素のアドレスです。
+    ///
+    /// # 戻り値
+    ///
+    /// 解放に成功した際はtrue、不正なアドレスだった際はfalseが返ります。
+    fn dealloc(&mut self, addr: PoolAddr) -> bool {
+        let slot = addr.slot;
+        match self.pools.get_mut(addr.bucket as usize) {
+            Some(pool) => match Self::slot_ptr(pool, slot) {
+                Some(ptr) => pool.dealloc(ptr),
+                None => false,
+            },
+            None => false,
+        }
+    }
+
+    /// アドレスが指す要素を読み取ります。
+    ///
+    /// # 安全性
+    ///
+    /// `addr`が確保済みで、`T`が対応するバケットの要素サイズ・整列長に
+    /// 収まっていることを呼び出し側が保証する必要があります。
+    unsafe fn read<T>(&self, addr: PoolAddr) -> Option<T> {
+        let pool = self.pools.get(addr.bucket as usize)?;
+        let ptr = Self::slot_ptr(pool, addr.slot)?;
+        Some(ptr.cast::<T>().read())
+    }
+
+    /// アドレスが指す要素を書き換えます。
+    ///
+    /// # 安全性
+    ///
+    /// `read`と同様の制約を満たす必要があります。
+    ///
+    /// # 戻り値
+    ///
+    /// 書き換えに成功した際はtrue、不正なアドレスだった際はfalseが返ります。
+    unsafe fn modify<T>(&mut self, addr: PoolAddr, value: T) -> bool {
+        let pool = match self.pools.get(addr.bucket as usize) {
+            Some(pool) => pool,
+            None => return false,
+        };
+        match Self::slot_ptr(pool, addr.slot) {
+            Some(ptr) => {
+                ptr.cast::<T>().write(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// バケット内のスロット番号からポインタを求めます。
+    fn slot_ptr(pool: &Pool, slot: u32) -> Option<*mut u8> {
+        if slot as usize >= pool.elements_count {
+            return None;
+        }
+        Some(unsafe { pool.buffer.add(slot as usize * pool.element_size) })
+    }
+}